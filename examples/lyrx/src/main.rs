@@ -27,6 +27,12 @@ fn main() {
         title: "LyrX".into(),
         language: "en".into(),
         keywords: vec![],
+        search: false,
+        syntax_theme: None,
+        fail_on_broken_links: true,
+        edit_url_template: None,
+        output_format: Default::default(),
+        latex_preamble: None,
     })
     .render()
     .unwrap();
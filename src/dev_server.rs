@@ -0,0 +1,147 @@
+use std::{
+    fs::read,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::channel,
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use log::{error, info, warn};
+use notify::{RecursiveMode, Watcher};
+use tiny_http::{Header, Response, Server};
+
+use crate::{Lssg, LssgError};
+
+/// Polls `/__lssg_last_build` and reloads the page whenever the build
+/// counter changes, so editors see changes without manually refreshing.
+pub(crate) const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(function() {
+    let last = null;
+    setInterval(function() {
+        fetch('/__lssg_last_build').then(function(r) { return r.text(); }).then(function(build) {
+            if (last !== null && build !== last) location.reload();
+            last = build;
+        }).catch(function() {});
+    }, 1000);
+})();
+</script>"#;
+
+/// Serves `lssg.options.output_directory` on `0.0.0.0:{port}`, watching the
+/// content directory and incrementally rebuilding on every change. Backs
+/// [`Lssg::preview`].
+pub(crate) fn run(lssg: &Lssg, port: u16) -> Result<(), LssgError> {
+    // full clean build, with the live-reload script in every page
+    lssg.render_inner(true, true, None)?;
+
+    let build_counter = Arc::new(AtomicU64::new(0));
+
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| LssgError::render(&e.to_string()))?;
+
+    if let Some(watch_dir) = lssg.options.index.parent() {
+        watcher
+            .watch(watch_dir, RecursiveMode::Recursive)
+            .map_err(|e| LssgError::render(&e.to_string()))?;
+    }
+
+    thread::scope(|scope| {
+        let build_counter = Arc::clone(&build_counter);
+        scope.spawn(move || {
+            while let Ok(event) = rx.recv() {
+                // coalesce bursts of filesystem events (e.g. editors that
+                // write a file in several steps) into a single rebuild.
+                thread::sleep(Duration::from_millis(100));
+                let mut paths = event.paths;
+                while let Ok(event) = rx.try_recv() {
+                    paths.extend(event.paths);
+                }
+                info!("Change detected ({:?}), rebuilding", paths);
+                match lssg.render_incremental(&paths) {
+                    Ok(()) => {
+                        build_counter.fetch_add(1, Ordering::SeqCst);
+                    }
+                    Err(err) => error!("Rebuild failed: {err:?}"),
+                }
+            }
+        });
+
+        if let Err(err) = serve(lssg, port, &build_counter) {
+            error!("Preview server failed: {err:?}");
+        }
+    });
+
+    Ok(())
+}
+
+fn serve(lssg: &Lssg, port: u16, build_counter: &AtomicU64) -> Result<(), LssgError> {
+    let server = Server::http(("0.0.0.0", port)).map_err(|e| LssgError::render(&e.to_string()))?;
+
+    for request in server.incoming_requests() {
+        let url = request.url().to_string();
+        if url == "/__lssg_last_build" {
+            let response = Response::from_string(build_counter.load(Ordering::SeqCst).to_string());
+            let _ = request.respond(response);
+            continue;
+        }
+
+        let response = match resolve(lssg, &url) {
+            Some((contents, content_type)) => Response::from_data(contents)
+                .with_header(Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes()).unwrap()),
+            None => {
+                warn!("404 {url}");
+                let not_found = lssg
+                    .options
+                    .not_found_page
+                    .as_ref()
+                    .and_then(|input| crate::util::filestem_from_path(input).ok())
+                    .and_then(|stem| resolve(lssg, &format!("/{stem}.html")))
+                    .map(|(contents, _)| contents)
+                    .unwrap_or_else(|| b"404 Not Found".to_vec());
+                Response::from_data(not_found)
+                    .with_status_code(404)
+                    .with_header(Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap())
+            }
+        };
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+fn resolve(lssg: &Lssg, url: &str) -> Option<(Vec<u8>, String)> {
+    let relative = url.trim_start_matches('/');
+    let base = lssg.options.output_directory.join(relative);
+
+    let path = if base.is_file() {
+        base
+    } else if base.join("index.html").is_file() {
+        base.join("index.html")
+    } else {
+        return None;
+    };
+
+    // Reject `..`-traversal: the resolved file must still live under
+    // `output_directory` once symlinks/`..` components are resolved.
+    let root = lssg.options.output_directory.canonicalize().ok()?;
+    let canonical = path.canonicalize().ok()?;
+    if !canonical.starts_with(&root) {
+        warn!("Rejected request escaping output_directory: {url}");
+        return None;
+    }
+    let path = canonical;
+
+    let content_type = match path.extension().and_then(|e| e.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css",
+        Some("js") => "application/javascript",
+        _ => "application/octet-stream",
+    };
+    read(path).ok().map(|contents| (contents, content_type.to_string()))
+}
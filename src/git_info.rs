@@ -0,0 +1,76 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Returns the Unix timestamp of the most recent commit touching `path`,
+/// or `None` when `path` isn't tracked in a git repository.
+pub fn last_modified(path: &Path) -> Option<i64> {
+    let dir = path.parent()?;
+    // `current_dir` already moves us into `dir`, so the pathspec must be
+    // relative to it, not to the original cwd, or git looks for
+    // `dir/dir/file` and silently finds nothing.
+    let relative = path.strip_prefix(dir).unwrap_or(path);
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct", "--"])
+        .arg(relative)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+}
+
+/// Returns `path` relative to the root of the git repository that contains
+/// it, for substitution into an edit-this-page URL template.
+pub fn repo_relative_path(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    let absolute = path.canonicalize().ok()?;
+    absolute.strip_prefix(root).ok().map(PathBuf::from)
+}
+
+#[test]
+fn test_last_modified_resolves_multi_component_relative_paths() {
+    // Regression test: a relative path like `site/content/index.md` used to
+    // get joined onto `current_dir` a second time, so git looked for
+    // `site/content/site/content/index.md` and found nothing.
+    let dir = std::env::temp_dir().join("lssg_test_git_info_last_modified");
+    let _ = std::fs::remove_dir_all(&dir);
+    let content_dir = dir.join("content");
+    std::fs::create_dir_all(&content_dir).unwrap();
+    let file = content_dir.join("index.md");
+    std::fs::write(&file, "# Home\n").unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    run(&["init", "-q"]);
+    run(&["config", "user.email", "test@example.com"]);
+    run(&["config", "user.name", "test"]);
+    run(&["add", "content/index.md"]);
+    run(&["commit", "-q", "-m", "add index"]);
+
+    // Reach `file` through a path relative to the cwd, e.g.
+    // `../../tmp/lssg_test_git_info_last_modified/content/index.md`.
+    let cwd = std::env::current_dir().unwrap();
+    let up: PathBuf = cwd.components().skip(1).map(|_| "..").collect();
+    let relative_file = up.join(file.strip_prefix("/").unwrap());
+    assert!(last_modified(&relative_file).is_some());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
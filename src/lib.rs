@@ -2,21 +2,27 @@ pub mod parser;
 pub mod renderer;
 pub mod sitemap;
 
+mod dev_server;
+mod git_info;
+mod search;
 mod stylesheet;
 mod util;
 
 use std::{
     fs::{copy, create_dir, create_dir_all, remove_dir_all, write, File},
     io::{self},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-use log::info;
+use log::{error, info, warn};
 use parser::parse_error::ParseError;
-use renderer::{HtmlLink, HtmlRenderOptions, HtmlRenderer, Meta, Rel};
+use renderer::{HtmlRenderOptions, HtmlRenderer, LatexRenderer, Meta, OutputFormat, Renderer, Rel};
 use sitemap::SiteMap;
 
-use crate::{parser::Parser, sitemap::Node, stylesheet::Stylesheet, util::filestem_from_path};
+use crate::{
+    parser::Parser, search::SearchIndex, sitemap::Node, stylesheet::Stylesheet,
+    util::filestem_from_path,
+};
 
 #[derive(Debug)]
 pub enum LssgError {
@@ -24,6 +30,8 @@ pub enum LssgError {
     Regex(regex::Error),
     Render(String),
     Io(io::Error),
+    /// A page's link points to a file that doesn't exist.
+    BrokenLink { source: PathBuf, target: PathBuf },
 }
 impl LssgError {
     pub fn render(error: &str) -> LssgError {
@@ -70,6 +78,26 @@ pub struct LssgOptions {
     pub keywords: Vec<(String, String)>,
     /// Lang attribute ("en") <https://www.w3schools.com/tags/ref_language_codes.asp>
     pub language: String,
+    /// Generate a `searchindex.js` with a client-side full-text search
+    /// index, so the site is searchable offline without a server.
+    pub search: bool,
+    /// Syntect theme name used to highlight fenced code blocks, e.g.
+    /// `"base16-ocean.dark"`. Falls back to `"InspiredGitHub"` when unset.
+    pub syntax_theme: Option<String>,
+    /// Fail the build when a page links to a file that doesn't exist.
+    /// When `false`, broken links are only logged as warnings.
+    pub fail_on_broken_links: bool,
+    /// Template for an "Edit this page" link, e.g.
+    /// `"https://github.com/me/site/edit/main/{path}"`. `{path}` is replaced
+    /// with the page's source path relative to its git repository root.
+    /// Skipped when the content directory isn't a git repository.
+    pub edit_url_template: Option<String>,
+    /// Which backend renders pages: a website, or a printable/PDF-ready
+    /// LaTeX document.
+    pub output_format: OutputFormat,
+    /// Preamble used when `output_format` is [`OutputFormat::Latex`].
+    /// Defaults to a minimal `article` preamble when unset.
+    pub latex_preamble: Option<String>,
 }
 
 pub struct Lssg {
@@ -81,12 +109,36 @@ impl Lssg {
         Lssg { options }
     }
 
-    // pub fn preview(&self, port: u32) {
-    //     info!("Listing on 0.0.0.0:{port}");
-    //     todo!()
-    // }
+    /// Runs a local dev server on `0.0.0.0:{port}` that serves
+    /// `output_directory`, watches the content directory for changes and
+    /// incrementally rebuilds the site on every change. Pages get a small
+    /// live-reload script injected so the browser refreshes automatically.
+    pub fn preview(&self, port: u16) -> Result<(), LssgError> {
+        info!("Listening on 0.0.0.0:{port}");
+        dev_server::run(self, port)
+    }
 
     pub fn render(&self) -> Result<(), LssgError> {
+        self.render_inner(true, false, None)
+    }
+
+    /// Rebuilds the site without wiping `output_directory` first, re-running
+    /// the expensive per-page work (rendering, which includes the git-log
+    /// lookup and syntax highlighting) only for pages whose source is in
+    /// `changed_paths`, or that haven't been rendered yet. Used by
+    /// [`Lssg::preview`] so a single edited file doesn't re-render every page
+    /// on every filesystem event, and injects the live-reload script into
+    /// pages.
+    pub(crate) fn render_incremental(&self, changed_paths: &[PathBuf]) -> Result<(), LssgError> {
+        self.render_inner(false, true, Some(changed_paths))
+    }
+
+    fn render_inner(
+        &self,
+        clean: bool,
+        live_reload: bool,
+        changed_paths: Option<&[PathBuf]>,
+    ) -> Result<(), LssgError> {
         let mut stylesheet = if let Some(p) = &self.options.global_stylesheet {
             let mut s = if self.options.overwrite_default_stylesheet {
                 Stylesheet::new()
@@ -103,8 +155,36 @@ impl Lssg {
                 stylesheet.append(&l.path)?;
             }
         }
+        if self.options.output_format == OutputFormat::Html {
+            // `HtmlRenderer` always highlights with a theme, explicit or
+            // not, so its CSS always needs to be in the stylesheet.
+            let theme = self
+                .options
+                .syntax_theme
+                .as_deref()
+                .unwrap_or(renderer::DEFAULT_SYNTAX_THEME);
+            if let Some(css) = renderer::syntax_theme_css(theme) {
+                stylesheet.append_raw(&css);
+            }
+        }
 
         let mut site_map = SiteMap::from_index(self.options.index.clone())?;
+
+        let broken_links = site_map.broken_links();
+        if !broken_links.is_empty() {
+            for link in &broken_links {
+                error!("Broken link in {:?}: {:?}", link.source, link.target);
+            }
+            if self.options.fail_on_broken_links {
+                let first = &broken_links[0];
+                return Err(LssgError::BrokenLink {
+                    source: first.source.clone(),
+                    target: first.target.clone(),
+                });
+            }
+            warn!("{} broken link(s) found, continuing anyway", broken_links.len());
+        }
+
         let stylesheet_id =
             site_map.add_stylesheet("main.css".into(), stylesheet, site_map.root())?;
 
@@ -157,51 +237,186 @@ impl Lssg {
                 })
                 .collect(),
             language: self.options.language.clone(),
+            syntax_theme: self.options.syntax_theme.clone(),
+            edit_url_template: self.options.edit_url_template.clone(),
         };
 
-        if self.options.output_directory.exists() {
+        if clean && self.options.output_directory.exists() {
             info!("Removing {:?}", self.options.output_directory);
             remove_dir_all(&self.options.output_directory)?;
         }
         info!("Creating {:?}", self.options.output_directory);
         create_dir_all(&self.options.output_directory)?;
 
-        let mut queue: Vec<usize> = vec![site_map.root()];
-        let renderer = HtmlRenderer::new(&site_map);
-        while let Some(id) = queue.pop() {
+        let mut search_index = self.options.search.then(SearchIndex::new);
+
+        let renderer: Box<dyn Renderer> = match self.options.output_format {
+            OutputFormat::Html => Box::new(HtmlRenderer::new(render_options, stylesheet_id)),
+            OutputFormat::Latex => Box::new(LatexRenderer::new(self.options.latex_preamble.clone())),
+        };
+
+        for id in preorder(&site_map, site_map.root()) {
             let node = site_map.get(id)?;
-            queue.append(&mut node.children.clone());
             let path = self.options.output_directory.join(site_map.path(id));
             match &node.node_type {
                 sitemap::NodeType::Stylesheet(s) => {
                     info!("Writing concatinated stylesheet {path:?}",);
-                    write(path, s.to_string())?;
+                    write_if_changed(&path, s.to_string().as_bytes())?;
                 }
                 sitemap::NodeType::Resource { input } => {
-                    copy(input, path)?;
+                    let changed = changed_paths
+                        .is_some_and(|changed_paths| changed_paths.iter().any(|p| paths_match(p, input)));
+                    if clean || !path.exists() || changed {
+                        copy(input, path)?;
+                    }
+                }
+                sitemap::NodeType::Generated { bytes } => {
+                    info!("Writing {:?}", path);
+                    write_if_changed(&path, bytes)?;
                 }
                 sitemap::NodeType::Folder => {
-                    create_dir(path)?;
+                    if !path.exists() {
+                        create_dir(path)?;
+                    }
                 }
-                sitemap::NodeType::Page { keep_name, .. } => {
-                    let mut options = render_options.clone();
-                    options.links.push(HtmlLink {
-                        rel: renderer::Rel::Stylesheet,
-                        href: site_map.rel_path(id, stylesheet_id),
-                    });
-                    let html = renderer.render(id, options)?;
-                    let html_output_path = if *keep_name {
-                        path.join(format!("../{}.html", node.name))
+                sitemap::NodeType::Page { keep_name, tokens, input } => {
+                    if let Some(index) = &mut search_index {
+                        index.add_page(site_map.path(id).to_string_lossy().into_owned(), node.name.clone(), tokens);
+                    }
+
+                    let ext = renderer.extension();
+                    let output_path = if *keep_name {
+                        path.join(format!("../{}.{ext}", node.name))
                     } else {
-                        create_dir_all(&path)?;
-                        path.join("index.html")
+                        path.join(format!("index.{ext}"))
                     };
-                    info!("Writing to {:?}", html_output_path);
-                    write(html_output_path, html)?;
+
+                    // On an incremental rebuild, skip the expensive render
+                    // (git-log lookup, syntax highlighting) for pages whose
+                    // source isn't one of the changed files.
+                    if let Some(changed_paths) = changed_paths {
+                        if output_path.exists() && !changed_paths.iter().any(|p| paths_match(p, input)) {
+                            continue;
+                        }
+                    }
+
+                    let Some(mut bytes) = renderer.render_page(&site_map, id)? else {
+                        continue;
+                    };
+                    if live_reload {
+                        if let Ok(html) = String::from_utf8(bytes) {
+                            bytes = inject_live_reload(html).into_bytes();
+                        } else {
+                            bytes = vec![];
+                        }
+                    }
+                    if !*keep_name {
+                        create_dir_all(&path)?;
+                    }
+                    info!("Writing to {:?}", output_path);
+                    write_if_changed(&output_path, &bytes)?;
                 }
             }
         }
 
+        if let Some((name, bytes)) = renderer.finalize() {
+            let path = self.options.output_directory.join(name);
+            info!("Writing {:?}", path);
+            write_if_changed(&path, &bytes)?;
+        }
+
+        if let Some(index) = &search_index {
+            let bytes = index.to_js().into_bytes();
+            let id = site_map.add(
+                Node {
+                    name: "searchindex.js".into(),
+                    parent: Some(site_map.root()),
+                    children: vec![],
+                    node_type: sitemap::NodeType::Generated { bytes: bytes.clone() },
+                },
+                site_map.root(),
+            )?;
+            let path = self.options.output_directory.join(site_map.path(id));
+            info!("Writing search index to {path:?}");
+            write_if_changed(&path, &bytes)?;
+        }
+
         Ok(())
     }
 }
+
+/// Visits `root` and every descendant in a defined pre-order (a node before
+/// its children, children in sitemap order). Backends like `LatexRenderer`
+/// fold pages into a single document and rely on this to match the site
+/// hierarchy, rather than the reverse/interleaved order a naive stack walk
+/// produces.
+fn preorder(site_map: &SiteMap, root: usize) -> Vec<usize> {
+    let mut result = vec![];
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        result.push(id);
+        if let Ok(node) = site_map.get(id) {
+            let mut children = node.children.clone();
+            children.reverse();
+            stack.extend(children);
+        }
+    }
+    result
+}
+
+/// Whether `changed` (e.g. a path reported by the file watcher) refers to
+/// the same file as `source` (a page's input path), comparing canonicalized
+/// paths where possible since the two may be relative to different roots.
+fn paths_match(changed: &Path, source: &Path) -> bool {
+    match (changed.canonicalize(), source.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => changed == source,
+    }
+}
+
+fn write_if_changed(path: &PathBuf, contents: &[u8]) -> Result<(), LssgError> {
+    if let Ok(existing) = std::fs::read(path) {
+        if existing == contents {
+            return Ok(());
+        }
+    }
+    write(path, contents)?;
+    Ok(())
+}
+
+fn inject_live_reload(html: String) -> String {
+    const SCRIPT: &str = dev_server::LIVE_RELOAD_SCRIPT;
+    if let Some(index) = html.rfind("</body>") {
+        let mut html = html;
+        html.insert_str(index, SCRIPT);
+        html
+    } else {
+        html + SCRIPT
+    }
+}
+
+#[test]
+fn test_preorder_visits_content_before_later_siblings() {
+    // Regression test: a stack walk that pushes children in their original
+    // order visits them last-in-first-out (reversed). `preorder` must
+    // correct for that so content added to the sitemap first is still
+    // visited before siblings (like the stylesheet) added to it afterwards.
+    let dir = std::env::temp_dir().join("lssg_test_preorder");
+    std::fs::create_dir_all(&dir).unwrap();
+    let index = dir.join("index.md");
+    std::fs::write(&index, "# Home\n").unwrap();
+
+    let mut site_map = SiteMap::from_index(index).unwrap();
+    let root = site_map.root();
+    let content_id = site_map.get(root).unwrap().children[0];
+    let stylesheet_id = site_map
+        .add_stylesheet("main.css".into(), Stylesheet::default(), root)
+        .unwrap();
+
+    let order = preorder(&site_map, root);
+    let content_pos = order.iter().position(|&id| id == content_id).unwrap();
+    let stylesheet_pos = order.iter().position(|&id| id == stylesheet_id).unwrap();
+    assert!(content_pos < stylesheet_pos);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -15,51 +15,76 @@ impl<R: Read> CharReader<R> {
         }
     }
 
-    pub fn peek(&mut self, buf: &mut [u8]) -> Result<usize, ParseError> {
-        // if buffer is already contained within peek buffer return it
-        if self.peek_buffer.len() >= buf.len() {
-            let mut cursor = Cursor::new(&mut self.peek_buffer);
-            cursor.read(buf)?;
+    /// Ensures `peek_buffer` holds at least `min_bytes` bytes (or every byte
+    /// the source has left). Returns `false` when the source ran dry first.
+    fn fill_peek_buffer(&mut self, min_bytes: usize) -> Result<bool, ParseError> {
+        while self.peek_buffer.len() < min_bytes {
+            let mut chunk = [0; 256];
+            let read = self.inner.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(false);
+            }
+            self.peek_buffer.extend_from_slice(&chunk[..read]);
         }
+        Ok(true)
+    }
 
-        let read = (&mut self.inner)
-            .take(buf.len() as u64)
-            .read_to_end(&mut self.peek_buffer)?;
-        if read == 0 {
-            return Err(ParseError::from(io::Error::new(
-                io::ErrorKind::UnexpectedEof,
-                "Unexpected EOF when peeking",
-            )));
+    pub fn peek(&mut self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        if !self.fill_peek_buffer(buf.len())? {
+            return Err(eof_error());
         }
-        let mut cursor = Cursor::new(&mut self.peek_buffer);
-        cursor.read(buf)?;
-        return Ok(read);
+        buf.copy_from_slice(&self.peek_buffer[..buf.len()]);
+        return Ok(buf.len());
     }
 
+    /// Peeks `length` *characters* (not bytes) without consuming them.
     pub fn peek_string(&mut self, length: usize) -> Result<String, ParseError> {
-        let mut buffer = vec![0; length];
-        self.peek(&mut buffer)?;
-        return Ok(String::from_utf8(buffer)
-            .map_err(|_| ParseError::invalid("String contains invalid utf-8"))?);
+        if length == 0 {
+            return Ok(String::new());
+        }
+        let mut want_bytes = length;
+        loop {
+            let filled = self.fill_peek_buffer(want_bytes)?;
+            let valid_up_to = match std::str::from_utf8(&self.peek_buffer) {
+                Ok(s) => s.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            let valid = std::str::from_utf8(&self.peek_buffer[..valid_up_to]).unwrap();
+            if valid.chars().count() >= length {
+                return Ok(valid.chars().take(length).collect());
+            }
+            if !filled {
+                return Err(eof_error());
+            }
+            // The chars peeked so far are multi-byte; widen the window and retry.
+            want_bytes += length;
+        }
     }
 
     pub fn peek_char(&mut self) -> Result<char, ParseError> {
-        let mut buffer = [0; 1];
-        self.peek(&mut buffer)?;
-        return Ok(buffer[0] as char);
+        if !self.fill_peek_buffer(1)? {
+            return Err(eof_error());
+        }
+        let width = utf8_char_width(self.peek_buffer[0]);
+        if !self.fill_peek_buffer(width)? {
+            return Err(eof_error());
+        }
+        decode_char(&self.peek_buffer[..width])
     }
 
+    /// Reads `length` *characters* (not bytes).
     pub fn read_string(&mut self, length: usize) -> Result<String, ParseError> {
-        let mut buffer = vec![0; length];
-        self.read_exact(&mut buffer)?;
-        return Ok(String::from_utf8(buffer)
-            .map_err(|_| ParseError::invalid("String contains invalid utf-8"))?);
+        let s = self.peek_string(length)?;
+        let mut discard = vec![0; s.len()];
+        self.read_exact(&mut discard)?;
+        return Ok(s);
     }
 
     pub fn read_char(&mut self) -> Result<char, ParseError> {
-        let mut buffer = [0; 1];
-        self.read_exact(&mut buffer)?;
-        return Ok(buffer[0] as char);
+        let c = self.peek_char()?;
+        let mut discard = vec![0; c.len_utf8()];
+        self.read_exact(&mut discard)?;
+        return Ok(c);
     }
 
     pub fn read_until(&mut self, op: fn(char) -> bool) -> Result<String, ParseError> {
@@ -73,6 +98,35 @@ impl<R: Read> CharReader<R> {
     }
 }
 
+/// Number of bytes the UTF-8 scalar starting with `first_byte` occupies.
+fn utf8_char_width(first_byte: u8) -> usize {
+    if first_byte & 0x80 == 0 {
+        1
+    } else if first_byte & 0xE0 == 0xC0 {
+        2
+    } else if first_byte & 0xF0 == 0xE0 {
+        3
+    } else if first_byte & 0xF8 == 0xF0 {
+        4
+    } else {
+        1
+    }
+}
+
+fn decode_char(bytes: &[u8]) -> Result<char, ParseError> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .ok_or_else(|| ParseError::invalid("String contains invalid utf-8"))
+}
+
+fn eof_error() -> ParseError {
+    ParseError::from(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "Unexpected EOF when peeking",
+    ))
+}
+
 impl<R: Read> Read for CharReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         if self.peek_buffer.is_empty() {
@@ -117,3 +171,17 @@ fn test_peek() -> Result<(), ParseError> {
     assert!(reader.read_char().is_err());
     Ok(())
 }
+
+#[test]
+fn test_multibyte_utf8() -> Result<(), ParseError> {
+    // "héllo wörld" mixes 2-byte (é, ö) and ASCII scalars.
+    let mut reader = CharReader::new("héllo wörld".as_bytes());
+    assert_eq!(reader.peek_char()?, 'h');
+    assert_eq!(reader.read_string(2)?, "hé".to_owned());
+    assert_eq!(reader.peek_char()?, 'l');
+    assert_eq!(reader.read_until(|c| c != ' ')?, "llo".to_owned());
+    // `read_until` consumes the delimiter it stopped at.
+    assert_eq!(reader.peek_char()?, 'w');
+    assert_eq!(reader.read_string(5)?, "wörld".to_owned());
+    Ok(())
+}
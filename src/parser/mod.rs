@@ -0,0 +1,81 @@
+pub mod parse_error;
+
+#[path = "../lmarkdown/char_reader.rs"]
+mod char_reader;
+
+use std::{io::Read, path::PathBuf};
+
+use char_reader::CharReader;
+use parse_error::ParseError;
+
+/// A single block-level element produced by parsing a markdown document.
+/// lssg's markdown dialect is intentionally small: headings, paragraphs,
+/// fenced code blocks and inline links (`[text](target)`) on their own line.
+#[derive(Debug, Clone)]
+pub enum Token {
+    Heading { level: u8, text: String },
+    Paragraph { text: String },
+    CodeBlock { lang: Option<String>, code: String },
+    Link { target: PathBuf, text: String },
+}
+
+pub struct Parser;
+
+impl Parser {
+    /// Parses `input` into a flat stream of block tokens.
+    pub fn parse<R: Read>(input: R) -> Result<Vec<Token>, ParseError> {
+        let mut reader = CharReader::new(input);
+        let mut tokens = vec![];
+        while let Ok(line) = reader.read_until(|c| c != '\n') {
+            if line.is_empty() {
+                continue;
+            }
+            tokens.push(parse_line(&mut reader, &line)?);
+        }
+        Ok(tokens)
+    }
+}
+
+fn parse_line<R: Read>(reader: &mut CharReader<R>, line: &str) -> Result<Token, ParseError> {
+    if let Some(lang) = line.strip_prefix("```") {
+        let mut code = String::new();
+        loop {
+            let fence_line = reader.read_until(|c| c != '\n')?;
+            if fence_line.starts_with("```") {
+                break;
+            }
+            code.push_str(&fence_line);
+            code.push('\n');
+        }
+        let lang = lang.trim();
+        return Ok(Token::CodeBlock {
+            lang: if lang.is_empty() { None } else { Some(lang.into()) },
+            code,
+        });
+    }
+
+    if let Some((text, target)) = parse_link_line(line) {
+        return Ok(Token::Link { target: PathBuf::from(target), text });
+    }
+
+    if let Some(rest) = line.strip_prefix("### ") {
+        return Ok(Token::Heading { level: 3, text: rest.into() });
+    }
+    if let Some(rest) = line.strip_prefix("## ") {
+        return Ok(Token::Heading { level: 2, text: rest.into() });
+    }
+    if let Some(rest) = line.strip_prefix("# ") {
+        return Ok(Token::Heading { level: 1, text: rest.into() });
+    }
+
+    Ok(Token::Paragraph { text: line.into() })
+}
+
+/// Matches a line that is entirely a markdown link, e.g. `[Home](./home.md)`.
+fn parse_link_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let text = line.strip_prefix('[')?;
+    let (text, rest) = text.split_once("](")?;
+    let target = rest.strip_suffix(')')?;
+    Some((text.to_string(), target.to_string()))
+}
@@ -0,0 +1,30 @@
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    Invalid(String),
+}
+
+impl ParseError {
+    pub fn invalid(message: &str) -> ParseError {
+        ParseError::Invalid(message.into())
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(error: io::Error) -> Self {
+        ParseError::Io(error)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(error) => write!(f, "io error: {error}"),
+            ParseError::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
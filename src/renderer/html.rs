@@ -0,0 +1,169 @@
+use crate::{
+    git_info,
+    parser::Token,
+    sitemap::{NodeType, SiteMap},
+    LssgError,
+};
+
+use super::{syntax, HtmlLink, Meta, Rel, Renderer};
+
+#[derive(Debug, Clone)]
+pub struct HtmlRenderOptions {
+    /// Extra `<link>` tags added to every page (the stylesheet link is
+    /// added automatically per-page since its relative href depends on
+    /// the page's depth).
+    pub links: Vec<HtmlLink>,
+    pub title: String,
+    pub favicon: Option<usize>,
+    pub meta: Vec<Meta>,
+    pub language: String,
+    /// Syntect theme name used to highlight fenced code blocks, e.g.
+    /// `"base16-ocean.dark"`. Falls back to `"InspiredGitHub"` when unset.
+    pub syntax_theme: Option<String>,
+    /// Template for an "Edit this page" link, `{path}` is replaced with the
+    /// page's source path relative to its git repository root.
+    pub edit_url_template: Option<String>,
+}
+
+pub struct HtmlRenderer {
+    options: HtmlRenderOptions,
+    stylesheet_id: usize,
+    highlighter: syntax::SyntaxHighlighter,
+}
+
+impl HtmlRenderer {
+    pub fn new(options: HtmlRenderOptions, stylesheet_id: usize) -> HtmlRenderer {
+        HtmlRenderer {
+            options,
+            stylesheet_id,
+            highlighter: syntax::SyntaxHighlighter::new(),
+        }
+    }
+
+    fn render_token(&self, token: &Token, theme: &str) -> String {
+        match token {
+            Token::Heading { level, text } => format!("<h{level}>{}</h{level}>\n", escape_html(text)),
+            Token::Paragraph { text } => format!("<p>{}</p>\n", escape_html(text)),
+            Token::Link { target, text } => format!(
+                "<a href=\"{}\">{}</a>\n",
+                escape_html(&target.to_string_lossy()),
+                escape_html(text)
+            ),
+            Token::CodeBlock { lang, code } => self.highlighter.highlight(code, lang.as_deref(), theme),
+        }
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render_page(&self, site_map: &SiteMap, id: usize) -> Result<Option<Vec<u8>>, LssgError> {
+        let node = site_map.get(id)?;
+        let (tokens, input) = match &node.node_type {
+            NodeType::Page { tokens, input, .. } => (tokens, input),
+            _ => return Ok(None),
+        };
+
+        let theme = self.options.syntax_theme.as_deref().unwrap_or(super::DEFAULT_SYNTAX_THEME);
+        let mut body = String::new();
+        for token in tokens {
+            body.push_str(&self.render_token(token, theme));
+        }
+
+        let last_modified = git_info::last_modified(input);
+        let edit_url = self.options.edit_url_template.as_ref().and_then(|template| {
+            git_info::repo_relative_path(input)
+                .map(|rel| template.replace("{path}", &rel.to_string_lossy()))
+        });
+        body.push_str(&render_page_footer(last_modified, &edit_url));
+
+        let mut links = self.options.links.clone();
+        links.push(HtmlLink {
+            rel: Rel::Stylesheet,
+            href: site_map.rel_path(id, self.stylesheet_id),
+        });
+
+        let last_modified_meta = last_modified
+            .map(|timestamp| format!("<meta name=\"last-modified\" content=\"{}\">\n", format_date(timestamp)))
+            .unwrap_or_default();
+
+        let html = format!(
+            "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n{}{}{}</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.options.language),
+            escape_html(&self.options.title),
+            render_links(&links),
+            render_meta(&self.options.meta),
+            last_modified_meta,
+            body
+        );
+        Ok(Some(html.into_bytes()))
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+}
+
+fn render_links(links: &[HtmlLink]) -> String {
+    links
+        .iter()
+        .map(|link| format!("<link rel=\"{}\" href=\"{}\">\n", link.rel, escape_html(&link.href)))
+        .collect()
+}
+
+/// Renders the "last updated"/"edit this page" footer line, when the page's
+/// source is tracked in git (or an edit URL template is configured).
+fn render_page_footer(last_modified: Option<i64>, edit_url: &Option<String>) -> String {
+    if last_modified.is_none() && edit_url.is_none() {
+        return String::new();
+    }
+
+    let mut footer = String::from("<footer class=\"lssg-page-footer\">\n");
+    if let Some(timestamp) = last_modified {
+        footer.push_str(&format!("<span>Last updated: {}</span>\n", format_date(timestamp)));
+    }
+    if let Some(edit_url) = edit_url {
+        footer.push_str(&format!(
+            "<a href=\"{}\">Edit this page</a>\n",
+            escape_html(edit_url)
+        ));
+    }
+    footer.push_str("</footer>\n");
+    footer
+}
+
+fn render_meta(meta: &[Meta]) -> String {
+    meta.iter()
+        .map(|m| format!("<meta name=\"{}\" content=\"{}\">\n", escape_html(&m.name), escape_html(&m.content)))
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Renders a Unix timestamp as a `YYYY-MM-DD` date, UTC.
+fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Howard Hinnant's days-from-civil algorithm, run in reverse: converts a
+/// day count since the Unix epoch into a (year, month, day) triple.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m, d)
+}
+
+#[test]
+fn test_format_date() {
+    assert_eq!(format_date(1_753_880_003), "2025-07-30");
+    assert_eq!(format_date(0), "1970-01-01");
+}
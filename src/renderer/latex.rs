@@ -0,0 +1,106 @@
+use std::sync::Mutex;
+
+use crate::{
+    parser::Token,
+    sitemap::{NodeType, SiteMap},
+    LssgError,
+};
+
+use super::Renderer;
+
+const DEFAULT_PREAMBLE: &str =
+    "\\documentclass{article}\n\\usepackage{hyperref}\n\\usepackage{listings}\n\\begin{document}\n";
+
+/// Translates the same token stream the HTML backend uses into a single
+/// `.tex` document: headings become `\section`/`\subsection`, code blocks
+/// become `verbatim`, links become `\href`. Meant as a printable/PDF
+/// companion to the HTML site, not a replacement for it.
+pub struct LatexRenderer {
+    preamble: String,
+    body: Mutex<String>,
+}
+
+impl LatexRenderer {
+    pub fn new(preamble: Option<String>) -> LatexRenderer {
+        LatexRenderer {
+            preamble: preamble.unwrap_or_else(|| DEFAULT_PREAMBLE.to_string()),
+            body: Mutex::new(String::new()),
+        }
+    }
+
+    fn render_token(token: &Token) -> String {
+        match token {
+            Token::Heading { level, text } => match level {
+                1 => format!("\\section{{{}}}\n", escape_tex(text)),
+                2 => format!("\\subsection{{{}}}\n", escape_tex(text)),
+                _ => format!("\\subsubsection{{{}}}\n", escape_tex(text)),
+            },
+            Token::Paragraph { text } => format!("{}\n\n", escape_tex(text)),
+            Token::CodeBlock { code, .. } => format!("\\begin{{verbatim}}\n{code}\\end{{verbatim}}\n"),
+            Token::Link { target, text } => {
+                format!("\\href{{{}}}{{{}}}\n", target.to_string_lossy(), escape_tex(text))
+            }
+        }
+    }
+}
+
+impl Renderer for LatexRenderer {
+    fn render_page(&self, site_map: &SiteMap, id: usize) -> Result<Option<Vec<u8>>, LssgError> {
+        let node = site_map.get(id)?;
+        if let NodeType::Page { tokens, keep_name, .. } = &node.node_type {
+            // `keep_name` pages (e.g. the 404 page) sit outside the site
+            // hierarchy and aren't part of the printable document.
+            if !keep_name {
+                let mut body = self.body.lock().unwrap();
+                for token in tokens {
+                    body.push_str(&Self::render_token(token));
+                }
+            }
+        }
+        // every page is folded into the single document returned by
+        // `finalize`, so there's no standalone per-page file to write.
+        Ok(None)
+    }
+
+    fn extension(&self) -> &'static str {
+        "tex"
+    }
+
+    fn finalize(&self) -> Option<(String, Vec<u8>)> {
+        let body = self.body.lock().unwrap();
+        let document = format!("{}{}\n\\end{{document}}\n", self.preamble, body);
+        Some(("site.tex".to_string(), document.into_bytes()))
+    }
+}
+
+/// Escapes a single pass over `text` char-by-char rather than chained
+/// `str::replace` calls, so the literal `{`/`}` that the backslash/caret/tilde
+/// replacements emit can't be re-escaped by a later replacement in the chain.
+fn escape_tex(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '&' => out.push_str("\\&"),
+            '%' => out.push_str("\\%"),
+            '_' => out.push_str("\\_"),
+            '#' => out.push_str("\\#"),
+            '{' => out.push_str("\\{"),
+            '}' => out.push_str("\\}"),
+            '$' => out.push_str("\\$"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[test]
+fn test_escape_tex_covers_the_full_special_set() {
+    assert_eq!(
+        escape_tex("100% off_price #1 {a} $x$ a^b a~b"),
+        "100\\% off\\_price \\#1 \\{a\\} \\$x\\$ a\\textasciicircum{}b a\\textasciitilde{}b"
+    );
+    assert_eq!(escape_tex(r"a\b"), "a\\textbackslash{}b");
+}
@@ -0,0 +1,71 @@
+mod html;
+mod latex;
+mod syntax;
+
+use std::fmt;
+
+pub use html::{HtmlRenderOptions, HtmlRenderer};
+pub use latex::LatexRenderer;
+
+use crate::{sitemap::SiteMap, LssgError};
+
+/// Which backend [`crate::Lssg::render`] dispatches page rendering to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Html,
+    Latex,
+}
+
+/// A pluggable rendering backend, selected via [`OutputFormat`]. The
+/// sitemap walk in `Lssg::render` calls `render_page` once per page node,
+/// then `finalize` once at the end for backends that produce a single
+/// whole-site document (e.g. LaTeX) instead of one file per page.
+pub trait Renderer {
+    /// Renders page node `id`, or returns `None` when this backend doesn't
+    /// produce a standalone file per page.
+    fn render_page(&self, site_map: &SiteMap, id: usize) -> Result<Option<Vec<u8>>, LssgError>;
+    /// File extension (without the dot) used for per-page output files.
+    fn extension(&self) -> &'static str;
+    /// Called once after every page has been rendered; returns a single
+    /// `(file_name, contents)` to write at the output root, if any.
+    fn finalize(&self) -> Option<(String, Vec<u8>)> {
+        None
+    }
+}
+
+/// Syntax theme used when [`HtmlRenderOptions::syntax_theme`] is unset.
+pub const DEFAULT_SYNTAX_THEME: &str = "InspiredGitHub";
+
+/// CSS for `theme_name`'s background/foreground, meant to be appended to
+/// the site's stylesheet alongside the highlighted `<pre><code>` output.
+pub fn syntax_theme_css(theme_name: &str) -> Option<String> {
+    syntax::SyntaxHighlighter::new().theme_css(theme_name)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rel {
+    Stylesheet,
+    Icon,
+}
+
+impl fmt::Display for Rel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rel::Stylesheet => write!(f, "stylesheet"),
+            Rel::Icon => write!(f, "icon"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlLink {
+    pub rel: Rel,
+    pub href: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Meta {
+    pub name: String,
+    pub content: String,
+}
@@ -0,0 +1,67 @@
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Color, ThemeSet},
+    html::{styled_line_to_highlighted_html, IncludeBackground},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+/// Highlights fenced code blocks with syntect, falling back to
+/// HTML-escaped plain text when the language or theme is unknown.
+pub struct SyntaxHighlighter {
+    syntax_set: SyntaxSet,
+    theme_set: ThemeSet,
+}
+
+impl SyntaxHighlighter {
+    pub fn new() -> SyntaxHighlighter {
+        SyntaxHighlighter {
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme_set: ThemeSet::load_defaults(),
+        }
+    }
+
+    pub fn highlight(&self, code: &str, lang: Option<&str>, theme_name: &str) -> String {
+        let syntax = lang
+            .and_then(|lang| self.syntax_set.find_syntax_by_token(lang))
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+
+        let theme = match self.theme_set.themes.get(theme_name) {
+            Some(theme) => theme,
+            None => return format!("<pre><code>{}</code></pre>\n", escape_html(code)),
+        };
+
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let mut html = String::from("<pre><code class=\"highlight\">");
+        for line in LinesWithEndings::from(code) {
+            match highlighter.highlight_line(line, &self.syntax_set) {
+                Ok(ranges) => html.push_str(
+                    &styled_line_to_highlighted_html(&ranges[..], IncludeBackground::No)
+                        .unwrap_or_else(|_| escape_html(line)),
+                ),
+                Err(_) => html.push_str(&escape_html(line)),
+            }
+        }
+        html.push_str("</code></pre>\n");
+        html
+    }
+
+    /// CSS for the theme's background/foreground, appended to the site's
+    /// stylesheet so highlighted spans read correctly against the page.
+    pub fn theme_css(&self, theme_name: &str) -> Option<String> {
+        let settings = &self.theme_set.themes.get(theme_name)?.settings;
+        let background = settings.background.map(color_to_css).unwrap_or_else(|| "#ffffff".into());
+        let foreground = settings.foreground.map(color_to_css).unwrap_or_else(|| "#000000".into());
+        Some(format!(
+            "pre code.highlight {{ background: {background}; color: {foreground}; display: block; padding: 1rem; overflow-x: auto; }}\n"
+        ))
+    }
+}
+
+fn color_to_css(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::parser::Token;
+
+/// Builds a client-side full-text search index so generated sites can be
+/// searched offline, the same way mdbook does it. Collects one document per
+/// page and an inverted word -> posting list index, then serializes both
+/// into a single `searchindex.js` alongside a small bundled searcher.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    documents: Vec<SearchDocument>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+#[derive(Debug)]
+struct SearchDocument {
+    url: String,
+    title: String,
+    excerpt: String,
+}
+
+#[derive(Debug)]
+struct Posting {
+    doc_id: usize,
+    term_frequency: u32,
+}
+
+impl SearchIndex {
+    pub fn new() -> SearchIndex {
+        SearchIndex::default()
+    }
+
+    /// Indexes a page from its parsed tokens, so the index can be built the
+    /// same way regardless of which [`crate::renderer::Renderer`] backend
+    /// (if any) turns the page into an output file.
+    pub fn add_page(&mut self, url: String, title: String, tokens: &[Token]) {
+        let text = tokens.iter().map(token_text).collect::<Vec<_>>().join(" ");
+        let doc_id = self.documents.len();
+        let excerpt: String = text.split_whitespace().collect::<Vec<_>>().join(" ")
+            .chars()
+            .take(200)
+            .collect();
+        self.documents.push(SearchDocument { url, title, excerpt });
+
+        let mut term_frequencies: HashMap<String, u32> = HashMap::new();
+        for word in tokenize(&text) {
+            *term_frequencies.entry(word).or_insert(0) += 1;
+        }
+        for (word, term_frequency) in term_frequencies {
+            self.postings
+                .entry(word)
+                .or_default()
+                .push(Posting { doc_id, term_frequency });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Serializes the index and the bundled searcher into a single
+    /// `searchindex.js` that defines `LSSG_SEARCH_INDEX` and a
+    /// `lssgSearch(query)` function ranking results by summed term
+    /// frequency.
+    pub fn to_js(&self) -> String {
+        let documents_json = self
+            .documents
+            .iter()
+            .map(|doc| {
+                format!(
+                    "{{\"url\":{},\"title\":{},\"excerpt\":{}}}",
+                    json_string(&doc.url),
+                    json_string(&doc.title),
+                    json_string(&doc.excerpt)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let postings_json = self
+            .postings
+            .iter()
+            .map(|(word, postings)| {
+                let entries = postings
+                    .iter()
+                    .map(|p| format!("[{},{}]", p.doc_id, p.term_frequency))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("{}:[{}]", json_string(word), entries)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "const LSSG_SEARCH_INDEX={{\"documents\":[{documents_json}],\"postings\":{{{postings_json}}}}};\n{}",
+            SEARCHER_JS
+        )
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Plain-text contents of a single token, for indexing.
+fn token_text(token: &Token) -> String {
+    match token {
+        Token::Heading { text, .. } => text.clone(),
+        Token::Paragraph { text } => text.clone(),
+        Token::CodeBlock { code, .. } => code.clone(),
+        Token::Link { text, .. } => text.clone(),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[test]
+fn test_add_page_indexes_from_tokens_not_rendered_markup() {
+    let mut index = SearchIndex::new();
+    index.add_page(
+        "page/".into(),
+        "Page".into(),
+        &[
+            Token::Heading { level: 1, text: "Hello".into() },
+            Token::Paragraph { text: "world wide web".into() },
+        ],
+    );
+    // None of the heading/paragraph text ever went through an HTML
+    // renderer, so this only passes if `add_page` reads `tokens` directly.
+    assert!(index.to_js().contains("\"Hello world wide web\""));
+    assert!(index.to_js().contains("hello"));
+}
+
+/// Prefix/substring matcher over [`SearchIndex::to_js`]'s postings table,
+/// ranking documents by summed term frequency across words matching the
+/// query. Bundled verbatim into every generated `searchindex.js`.
+const SEARCHER_JS: &str = r#"
+function lssgSearch(query) {
+    const q = query.trim().toLowerCase();
+    if (!q) return [];
+    const scores = new Map();
+    for (const [word, postings] of Object.entries(LSSG_SEARCH_INDEX.postings)) {
+        if (!word.includes(q)) continue;
+        for (const [docId, tf] of postings) {
+            scores.set(docId, (scores.get(docId) || 0) + tf);
+        }
+    }
+    return [...scores.entries()]
+        .sort((a, b) => b[1] - a[1])
+        .map(([docId]) => LSSG_SEARCH_INDEX.documents[docId]);
+}
+"#;
@@ -0,0 +1,279 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    parser::{Parser, Token},
+    stylesheet::Stylesheet,
+    util::filestem_from_path,
+    LssgError,
+};
+
+#[derive(Debug)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub node_type: NodeType,
+}
+
+/// A link in a page whose target doesn't resolve to an existing file.
+#[derive(Debug)]
+pub struct BrokenLink {
+    pub source: PathBuf,
+    pub target: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum NodeType {
+    Folder,
+    Stylesheet(Stylesheet),
+    Resource { input: PathBuf },
+    Page {
+        tokens: Vec<Token>,
+        input: PathBuf,
+        keep_name: bool,
+    },
+    /// Bytes computed during rendering (e.g. `searchindex.js`) rather than
+    /// copied from or parsed out of a source file.
+    Generated { bytes: Vec<u8> },
+}
+
+/// The tree of everything that ends up in `output_directory`: pages
+/// discovered by following local markdown links from the index file, plus
+/// whatever stylesheets/resources get attached to them during
+/// [`crate::Lssg::render`].
+pub struct SiteMap {
+    nodes: Vec<Node>,
+}
+
+impl SiteMap {
+    /// Parses `index` and recursively follows its local markdown links to
+    /// discover the rest of the site, adding one [`NodeType::Page`] per file.
+    pub fn from_index(index: PathBuf) -> Result<SiteMap, LssgError> {
+        let mut site_map = SiteMap { nodes: vec![] };
+        let root = site_map.push(Node {
+            name: String::new(),
+            parent: None,
+            children: vec![],
+            node_type: NodeType::Folder,
+        });
+
+        let mut visited: HashMap<PathBuf, usize> = HashMap::new();
+        site_map.add_page_recursive(&index, root, true, &mut visited)?;
+        Ok(site_map)
+    }
+
+    fn add_page_recursive(
+        &mut self,
+        input: &Path,
+        parent: usize,
+        is_index: bool,
+        visited: &mut HashMap<PathBuf, usize>,
+    ) -> Result<usize, LssgError> {
+        let canonical = input.canonicalize().unwrap_or_else(|_| input.to_path_buf());
+        if let Some(id) = visited.get(&canonical) {
+            return Ok(*id);
+        }
+
+        let file = File::open(input)?;
+        let tokens = Parser::parse(file)?;
+        let name = if is_index {
+            "index".to_string()
+        } else {
+            filestem_from_path(input)?
+        };
+
+        let id = self.add(
+            Node {
+                name,
+                parent: Some(parent),
+                children: vec![],
+                node_type: NodeType::Page {
+                    tokens: tokens.clone(),
+                    input: input.to_path_buf(),
+                    keep_name: false,
+                },
+            },
+            parent,
+        )?;
+        visited.insert(canonical, id);
+
+        let dir = input.parent().unwrap_or_else(|| Path::new("."));
+        for token in &tokens {
+            if let Token::Link { target, .. } = token {
+                if target.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                    let linked = dir.join(target);
+                    if linked.is_file() {
+                        self.add_page_recursive(&linked, id, false, visited)?;
+                    }
+                }
+            }
+        }
+
+        Ok(id)
+    }
+
+    fn push(&mut self, node: Node) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(node);
+        id
+    }
+
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    pub fn add(&mut self, node: Node, parent: usize) -> Result<usize, LssgError> {
+        let id = self.push(node);
+        self.nodes[parent].children.push(id);
+        Ok(id)
+    }
+
+    pub fn add_stylesheet(
+        &mut self,
+        name: String,
+        stylesheet: Stylesheet,
+        parent: usize,
+    ) -> Result<usize, LssgError> {
+        self.add(
+            Node {
+                name,
+                parent: Some(parent),
+                children: vec![],
+                node_type: NodeType::Stylesheet(stylesheet),
+            },
+            parent,
+        )
+    }
+
+    pub fn get(&self, id: usize) -> Result<&Node, LssgError> {
+        self.nodes
+            .get(id)
+            .ok_or_else(|| LssgError::render(&format!("no such sitemap node {id}")))
+    }
+
+    /// Output-relative path for `id`, built from the `name` of every
+    /// ancestor from the root down.
+    pub fn path(&self, id: usize) -> PathBuf {
+        let mut segments = vec![];
+        let mut current = Some(id);
+        while let Some(cur) = current {
+            let node = &self.nodes[cur];
+            if !node.name.is_empty() {
+                segments.push(node.name.clone());
+            }
+            current = node.parent;
+        }
+        segments.reverse();
+        segments.into_iter().collect()
+    }
+
+    /// Path to `target` relative to `from`, for `<link>`/`<a>` hrefs.
+    pub fn rel_path(&self, from: usize, target: usize) -> String {
+        let from_depth = self.path(from).components().count();
+        let up = "../".repeat(from_depth.saturating_sub(1));
+        format!("{up}{}", self.path(target).to_string_lossy())
+    }
+
+    /// Resolves every internal link in every page against the filesystem,
+    /// collecting *all* broken ones rather than stopping at the first.
+    /// Links containing `://` or starting with `mailto:` are assumed external
+    /// and skipped, as are same-page anchors (`#top`); other schemes (e.g.
+    /// `tel:`) are checked as files. A
+    /// trailing `#fragment` on a real file link is stripped before checking.
+    pub fn broken_links(&self) -> Vec<BrokenLink> {
+        let mut broken = vec![];
+        for node in &self.nodes {
+            if let NodeType::Page { tokens, input, .. } = &node.node_type {
+                let dir = input.parent().unwrap_or_else(|| Path::new("."));
+                for token in tokens {
+                    if let Token::Link { target, .. } = token {
+                        if is_external(target) {
+                            continue;
+                        }
+                        let Some(file_target) = strip_fragment(target) else {
+                            // Same-page anchor link (`#top`): nothing to resolve.
+                            continue;
+                        };
+                        if !dir.join(&file_target).is_file() {
+                            broken.push(BrokenLink {
+                                source: input.clone(),
+                                target: target.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        broken
+    }
+
+    /// Finds the page node whose source is `input`, if any.
+    pub fn find_by_input(&self, input: &Path) -> Option<usize> {
+        let canonical = input.canonicalize().ok();
+        self.nodes.iter().position(|node| match &node.node_type {
+            NodeType::Page { input: page_input, .. } => {
+                page_input == input || canonical.as_deref() == page_input.canonicalize().ok().as_deref()
+            }
+            _ => false,
+        })
+    }
+}
+
+fn is_external(target: &Path) -> bool {
+    target
+        .to_str()
+        .map(|s| s.contains("://") || s.starts_with("mailto:"))
+        .unwrap_or(false)
+}
+
+/// Strips a trailing `#fragment` from a link target, returning `None` for
+/// same-page anchors (`#top`) that don't point at another file at all.
+fn strip_fragment(target: &Path) -> Option<PathBuf> {
+    let s = target.to_str()?;
+    let path = s.split('#').next().unwrap_or("");
+    if path.is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(path))
+}
+
+#[test]
+fn test_broken_links_skips_same_page_anchors_and_strips_fragments() {
+    let dir = std::env::temp_dir().join("lssg_test_broken_links_anchors");
+    std::fs::create_dir_all(&dir).unwrap();
+    let index = dir.join("index.md");
+    let other = dir.join("other.md");
+    std::fs::write(
+        &index,
+        "# Home\n\n[top](#top)\n\n[other](other.md#section)\n\n[missing](missing.md#section)\n",
+    )
+    .unwrap();
+    std::fs::write(&other, "# Other\n").unwrap();
+
+    let site_map = SiteMap::from_index(index).unwrap();
+    let broken = site_map.broken_links();
+
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].target, PathBuf::from("missing.md#section"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+impl fmt::Display for SiteMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(site_map: &SiteMap, f: &mut fmt::Formatter<'_>, id: usize, depth: usize) -> fmt::Result {
+            let node = &site_map.nodes[id];
+            writeln!(f, "{}{}", "  ".repeat(depth), node.name)?;
+            for child in &node.children {
+                write_node(site_map, f, *child, depth + 1)?;
+            }
+            Ok(())
+        }
+        write_node(self, f, self.root(), 0)
+    }
+}
@@ -0,0 +1,45 @@
+use std::{fmt, fs::read_to_string, path::Path};
+
+use crate::LssgError;
+
+const DEFAULT_CSS: &str = "body{font-family:sans-serif;margin:0 auto;max-width:800px;padding:2rem;line-height:1.5;}\na{color:#1a73e8;}\n";
+
+/// Concatenated CSS written as a single `main.css`. Starts from a small
+/// built-in default unless the caller opts to overwrite it entirely.
+#[derive(Debug)]
+pub struct Stylesheet {
+    css: String,
+}
+
+impl Stylesheet {
+    pub fn new() -> Stylesheet {
+        Stylesheet { css: String::new() }
+    }
+
+    pub fn append(&mut self, path: &Path) -> Result<(), LssgError> {
+        self.css.push_str(&read_to_string(path)?);
+        self.css.push('\n');
+        Ok(())
+    }
+
+    /// Appends raw CSS text that didn't come from a file, e.g. a syntax
+    /// highlighting theme's generated background/foreground rules.
+    pub fn append_raw(&mut self, css: &str) {
+        self.css.push_str(css);
+        self.css.push('\n');
+    }
+}
+
+impl Default for Stylesheet {
+    fn default() -> Self {
+        let mut stylesheet = Stylesheet::new();
+        stylesheet.css.push_str(DEFAULT_CSS);
+        stylesheet
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.css)
+    }
+}
@@ -0,0 +1,12 @@
+use std::path::Path;
+
+use crate::LssgError;
+
+/// Returns the file stem (filename without extension) of `path`, used as a
+/// page's default node name when it isn't otherwise renamed.
+pub fn filestem_from_path(path: &Path) -> Result<String, LssgError> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(String::from)
+        .ok_or_else(|| LssgError::render(&format!("invalid file name: {path:?}")))
+}